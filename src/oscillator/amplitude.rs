@@ -0,0 +1,67 @@
+//!
+//! Amplitude envelopes for the Oscillator.
+//!
+
+/// A type that can produce an amplitude scalar for a given position along
+/// a note's playhead, in `[0,1]`.
+pub trait Amplitude {
+
+    /// The amplitude (typically in `[0,1]`) that should apply at the given
+    /// playhead percentage.
+    fn amp_at_playhead(&self, playhead_perc: f64) -> f32;
+
+}
+
+
+/// A single point in an amplitude envelope: a playhead percentage paired
+/// with the amplitude that should apply there.
+#[derive(Debug, Clone, Copy, RustcEncodable, RustcDecodable)]
+pub struct Point {
+    pub playhead_perc: f64,
+    pub amp: f32,
+}
+
+
+/// A piecewise-linear amplitude envelope, interpolating amplitude between
+/// its sorted `points`.
+#[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
+pub struct Envelope {
+    pub points: Vec<Point>,
+}
+
+impl Envelope {
+
+    /// Envelope constructor from a fixed, unchanging amplitude.
+    #[inline]
+    pub fn fixed(amp: f32) -> Envelope {
+        Envelope {
+            points: vec![Point { playhead_perc: 0.0, amp: amp }],
+        }
+    }
+
+}
+
+impl Amplitude for Envelope {
+
+    fn amp_at_playhead(&self, playhead_perc: f64) -> f32 {
+        if self.points.is_empty() {
+            return 0.0;
+        }
+
+        let mut prev = &self.points[0];
+        for point in self.points.iter() {
+            if point.playhead_perc > playhead_perc {
+                let span = point.playhead_perc - prev.playhead_perc;
+                if span <= 0.0 {
+                    return prev.amp;
+                }
+                let t = ((playhead_perc - prev.playhead_perc) / span) as f32;
+                return prev.amp + (point.amp - prev.amp) * t;
+            }
+            prev = point;
+        }
+
+        prev.amp
+    }
+
+}
@@ -0,0 +1,42 @@
+//!
+//! Frequency warping for the Oscillator.
+//!
+
+use std::f64::consts::PI;
+
+const TAU: f64 = PI * 2.0;
+
+
+/// A type used for warping the frequency fed into an Oscillator's phase
+/// advancement. Implementors are given a chance to step their own internal
+/// phase (stored outside the Oscillator, in the `freq_warp_phase` slot
+/// threaded through `next_phase`) and to shape the resulting hz from the
+/// carrier hz and that phase.
+pub trait FreqWarp {
+
+    /// Advance the warp's own internal phase by one sample.
+    fn step_phase(&self, sample_hz: f64, freq_warp_phase: &mut f64);
+
+    /// Return the (possibly warped) hz that should be used for this sample.
+    fn warp_hz(&self, hz: f64, freq_warp_phase: f64) -> f64;
+
+}
+
+
+/// The identity FreqWarp - passes the carrier hz through unchanged and
+/// never advances its phase. Used as the default `FW` type for Oscillators
+/// that have no need for frequency warping.
+#[derive(Debug, Clone, Copy, Default, RustcEncodable, RustcDecodable)]
+pub struct NoWarp;
+
+impl FreqWarp for NoWarp {
+
+    #[inline]
+    fn step_phase(&self, _sample_hz: f64, _freq_warp_phase: &mut f64) {}
+
+    #[inline]
+    fn warp_hz(&self, hz: f64, _freq_warp_phase: f64) -> f64 {
+        hz
+    }
+
+}
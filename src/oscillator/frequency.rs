@@ -0,0 +1,66 @@
+//!
+//! Frequency envelopes for the Oscillator.
+//!
+
+/// A type that can produce an hz value for a given position along a note's
+/// playhead, in `[0,1]`.
+pub trait Frequency {
+
+    /// The hz that should sound at the given playhead percentage.
+    fn hz_at_playhead(&self, playhead_perc: f64) -> f64;
+
+}
+
+
+/// A single point in a frequency envelope: a playhead percentage paired
+/// with the hz that should sound there.
+#[derive(Debug, Clone, Copy, RustcEncodable, RustcDecodable)]
+pub struct Point {
+    pub playhead_perc: f64,
+    pub hz: f64,
+}
+
+
+/// A piecewise-linear frequency envelope, interpolating hz between its
+/// sorted `points`.
+#[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
+pub struct Envelope {
+    pub points: Vec<Point>,
+}
+
+impl Envelope {
+
+    /// Envelope constructor from a fixed, unchanging hz.
+    #[inline]
+    pub fn fixed(hz: f64) -> Envelope {
+        Envelope {
+            points: vec![Point { playhead_perc: 0.0, hz: hz }],
+        }
+    }
+
+}
+
+impl Frequency for Envelope {
+
+    fn hz_at_playhead(&self, playhead_perc: f64) -> f64 {
+        if self.points.is_empty() {
+            return 0.0;
+        }
+
+        let mut prev = &self.points[0];
+        for point in self.points.iter() {
+            if point.playhead_perc > playhead_perc {
+                let span = point.playhead_perc - prev.playhead_perc;
+                if span <= 0.0 {
+                    return prev.hz;
+                }
+                let t = (playhead_perc - prev.playhead_perc) / span;
+                return prev.hz + (point.hz - prev.hz) * t;
+            }
+            prev = point;
+        }
+
+        prev.hz
+    }
+
+}
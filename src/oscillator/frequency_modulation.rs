@@ -0,0 +1,103 @@
+//!
+//! Frequency modulation, implemented as a `FreqWarp`.
+//!
+
+use std::cell::Cell;
+use std::f64::consts::PI;
+
+use super::freq_warp::FreqWarp;
+
+const TAU: f64 = PI * 2.0;
+
+
+/// A `FreqWarp` implementor giving DX7-style FM: the carrier hz is warped by
+/// a sinusoidal modulator whose own frequency tracks the carrier via
+/// `fm_multiplier`, and whose peak deviation is `fm_index` times that
+/// modulator frequency.
+///
+/// Drop this straight into an Oscillator's `freq_warp` slot to get FM tones
+/// without touching `Waveform` or `Amplitude` at all.
+#[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
+pub struct Fm {
+    /// Modulator frequency expressed as a ratio of the carrier hz.
+    pub fm_multiplier: f64,
+    /// Modulation depth - the peak deviation is `fm_index * modulator_hz`.
+    pub fm_index: f64,
+    /// The carrier hz as of the last `warp_hz` call, cached so that
+    /// `step_phase` can compute the modulator hz without it being passed in.
+    carrier_hz: Cell<f64>,
+}
+
+impl Fm {
+
+    /// Fm constructor.
+    #[inline]
+    pub fn new(fm_multiplier: f64, fm_index: f64) -> Fm {
+        Fm {
+            fm_multiplier: fm_multiplier,
+            fm_index: fm_index,
+            carrier_hz: Cell::new(0.0),
+        }
+    }
+
+    /// The modulator's own frequency, tracking the given carrier hz.
+    #[inline]
+    fn modulator_hz(&self, carrier_hz: f64) -> f64 {
+        carrier_hz * self.fm_multiplier
+    }
+
+}
+
+impl FreqWarp for Fm {
+
+    /// Advance the modulator phase by `modulator_hz / sample_hz`, wrapping
+    /// into `[0,1)` to avoid float blow-up over long notes. `next_phase`
+    /// calls this before `warp_hz`, so the carrier hz used here is the one
+    /// cached by the previous sample's `warp_hz` call (`0.0` before the
+    /// first, which leaves the modulator phase where it is).
+    #[inline]
+    fn step_phase(&self, sample_hz: f64, freq_warp_phase: &mut f64) {
+        let carrier_hz = self.carrier_hz.get();
+        let modulator_hz = self.modulator_hz(carrier_hz);
+        *freq_warp_phase = (*freq_warp_phase + modulator_hz / sample_hz).fract();
+    }
+
+    /// Return the instantaneous modulated frequency:
+    /// `hz + fm_index * modulator_hz * sin(TAU * freq_warp_phase)`.
+    /// `fm_index = 0` reproduces `hz` exactly.
+    #[inline]
+    fn warp_hz(&self, hz: f64, freq_warp_phase: f64) -> f64 {
+        self.carrier_hz.set(hz);
+        let modulator_hz = self.modulator_hz(hz);
+        hz + self.fm_index * modulator_hz * (TAU * freq_warp_phase).sin()
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_index_reproduces_hz_unchanged() {
+        let fm = Fm::new(2.0, 0.0);
+        let mut phase = 0.0;
+        for _ in 0..100 {
+            fm.step_phase(44100.0, &mut phase);
+            assert_eq!(fm.warp_hz(440.0, phase), 440.0);
+        }
+    }
+
+    #[test]
+    fn step_phase_wraps_into_unit_range() {
+        let fm = Fm::new(50.0, 1.0);
+        fm.warp_hz(440.0, 0.0);
+
+        let mut phase = 0.0;
+        for _ in 0..10000 {
+            fm.step_phase(44100.0, &mut phase);
+            assert!(phase >= 0.0 && phase < 1.0, "phase {} out of [0,1)", phase);
+        }
+    }
+}
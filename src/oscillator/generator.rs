@@ -0,0 +1,172 @@
+//!
+//! An Iterator wrapper that owns an Oscillator's playback state.
+//!
+
+use super::{phase_bend, Amplitude, FreqWarp, Frequency, Oscillator, PhaseBend, Waveform};
+use super::waveform::PitchLfo;
+
+
+/// Owns an `Oscillator` along with the phase, freq-warp phase, and playhead
+/// that would otherwise have to be threaded by hand through every call to
+/// `amp_at` / `next_phase`. Implements `Iterator<Item = f32>` so a buffer
+/// can be filled with `generator.take(frames).collect()`.
+#[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
+pub struct Generator<W, A, F, FW, PB = phase_bend::NoBend> {
+    /// The Oscillator being driven.
+    pub oscillator: Oscillator<W, A, F, FW, PB>,
+    /// Sample rate the generator advances at.
+    pub sample_hz: f64,
+    /// Multiplier applied to the oscillator's hz, e.g. for note pitch.
+    pub note_freq_multi: f64,
+    /// How long, in seconds, the playhead takes to go from 0.0 to 1.0.
+    pub note_duration_secs: f64,
+    /// When `true`, the playhead wraps at 1.0 instead of ending iteration.
+    pub is_continuous: bool,
+    /// Optional vibrato, applied to `note_freq_multi` before each call to
+    /// `Oscillator::next_phase`.
+    pub pitch_lfo: Option<PitchLfo>,
+    phase: f64,
+    freq_warp_phase: f64,
+    lfo_phase: f64,
+    playhead_perc: f64,
+}
+
+impl<W, A, F, FW, PB> Generator<W, A, F, FW, PB> {
+
+    /// Generator constructor, starting at phase 0 and the head of the note.
+    #[inline]
+    pub fn new(oscillator: Oscillator<W, A, F, FW, PB>,
+               sample_hz: f64,
+               note_freq_multi: f64,
+               note_duration_secs: f64) -> Generator<W, A, F, FW, PB> {
+        Generator {
+            oscillator: oscillator,
+            sample_hz: sample_hz,
+            note_freq_multi: note_freq_multi,
+            note_duration_secs: note_duration_secs,
+            is_continuous: false,
+            pitch_lfo: None,
+            phase: 0.0,
+            freq_warp_phase: 0.0,
+            lfo_phase: 0.0,
+            playhead_perc: 0.0,
+        }
+    }
+
+    /// Builder method making the generator loop forever rather than
+    /// stopping once the playhead passes 1.0.
+    #[inline]
+    pub fn continuous(mut self) -> Generator<W, A, F, FW, PB> {
+        self.is_continuous = true;
+        self
+    }
+
+    /// Builder method attaching vibrato, applied to `note_freq_multi`
+    /// before each sample's phase advance.
+    #[inline]
+    pub fn pitch_lfo(mut self, pitch_lfo: PitchLfo) -> Generator<W, A, F, FW, PB> {
+        self.pitch_lfo = Some(pitch_lfo);
+        self
+    }
+
+}
+
+impl<W, A, F, FW, PB> Iterator for Generator<W, A, F, FW, PB> where
+    W: Waveform,
+    A: Amplitude,
+    F: Frequency,
+    FW: FreqWarp,
+    PB: PhaseBend,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if !self.is_continuous && self.playhead_perc > 1.0 {
+            return None;
+        }
+
+        let sample = if self.oscillator.is_muted {
+            0.0
+        } else {
+            self.oscillator.amp_at(self.phase, self.playhead_perc)
+        };
+
+        let note_freq_multi = match self.pitch_lfo {
+            Some(ref lfo) => self.note_freq_multi * lfo.step(self.sample_hz, &mut self.lfo_phase),
+            None => self.note_freq_multi,
+        };
+
+        self.phase = self.oscillator.next_phase(self.phase,
+                                                 self.playhead_perc,
+                                                 note_freq_multi,
+                                                 self.sample_hz,
+                                                 &mut self.freq_warp_phase);
+
+        self.playhead_perc += 1.0 / (self.sample_hz * self.note_duration_secs);
+        if self.is_continuous && self.playhead_perc > 1.0 {
+            self.playhead_perc = self.playhead_perc.fract();
+        }
+
+        Some(sample)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{AmpEnvelope, FreqEnvelope, Oscillator};
+    use super::super::freq_warp::NoWarp;
+    use super::super::phase_bend::Bend;
+    use super::super::waveform::{PitchLfo, Sine};
+
+    fn osc() -> Oscillator<Sine, AmpEnvelope, FreqEnvelope, NoWarp> {
+        Oscillator::new(Sine, AmpEnvelope::fixed(1.0), FreqEnvelope::fixed(440.0), NoWarp)
+    }
+
+    #[test]
+    fn pitch_lfo_modulates_note_freq_multi() {
+        let plain: Vec<f32> = Generator::new(osc(), 44100.0, 1.0, 1.0).take(2000).collect();
+        let vibrato: Vec<f32> = Generator::new(osc(), 44100.0, 1.0, 1.0)
+            .pitch_lfo(PitchLfo::new(5.0, 12.0))
+            .take(2000)
+            .collect();
+
+        assert!(plain.iter().zip(vibrato.iter()).any(|(p, v)| (p - v).abs() > 1e-4));
+    }
+
+    #[test]
+    fn zero_depth_pitch_lfo_is_a_no_op() {
+        let plain: Vec<f32> = Generator::new(osc(), 44100.0, 1.0, 1.0).take(2000).collect();
+        let lfo: Vec<f32> = Generator::new(osc(), 44100.0, 1.0, 1.0)
+            .pitch_lfo(PitchLfo::new(5.0, 0.0))
+            .take(2000)
+            .collect();
+
+        assert_eq!(plain, lfo);
+    }
+
+    #[test]
+    fn terminates_once_playhead_passes_note_duration() {
+        // sample_hz=10, note_duration_secs=1.0 -> playhead advances by 0.1
+        // per sample, so the note should end after 11 samples.
+        let samples: Vec<f32> = Generator::new(osc(), 10.0, 1.0, 1.0).collect();
+        assert_eq!(samples.len(), 11);
+    }
+
+    #[test]
+    fn continuous_keeps_yielding_past_note_duration() {
+        let samples: Vec<f32> = Generator::new(osc(), 10.0, 1.0, 1.0)
+            .continuous()
+            .take(100)
+            .collect();
+        assert_eq!(samples.len(), 100);
+    }
+
+    #[test]
+    fn accepts_an_oscillator_with_a_non_default_phase_bend() {
+        let bent = osc().bend(Bend::new(0.2, 0.8));
+        let samples: Vec<f32> = Generator::new(bent, 44100.0, 1.0, 1.0).take(10).collect();
+        assert_eq!(samples.len(), 10);
+    }
+}
@@ -1,4 +1,4 @@
-//! 
+//!
 //! Synthesis Oscillator module.
 //!
 
@@ -8,16 +8,22 @@ pub use self::amplitude::Envelope as AmpEnvelope;
 pub use self::frequency::Frequency;
 pub use self::frequency::Envelope as FreqEnvelope;
 pub use self::freq_warp::FreqWarp;
+pub use self::frequency_modulation::Fm;
+pub use self::generator::Generator;
+pub use self::phase_bend::PhaseBend;
 
 pub mod waveform;
 pub mod amplitude;
 pub mod frequency;
 pub mod freq_warp;
+pub mod frequency_modulation;
+pub mod generator;
+pub mod phase_bend;
 
 
 /// The fundamental component of a synthesizer.
 #[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
-pub struct Oscillator<W, A, F, FW> {
+pub struct Oscillator<W, A, F, FW, PB = phase_bend::NoBend> {
     /// Waveform used for phase movement.
     pub waveform: W,
     /// Envelope for amplitude interpolation.
@@ -26,60 +32,86 @@ pub struct Oscillator<W, A, F, FW> {
     pub frequency: F,
     /// A type used for warping the Oscillator's frequency.
     pub freq_warp: FW,
+    /// A type used for bending the phase read by the Waveform.
+    pub phase_bend: PB,
     /// Whether or not the Oscillator is currently muted.
     pub is_muted: bool,
 }
 
 
-impl<W, A, F, FW> Oscillator<W, A, F, FW> {
+impl<W, A, F, FW, PB> Oscillator<W, A, F, FW, PB> {
 
-    /// Oscillator constructor.
+    /// Oscillator constructor. The phase bend is `PB::default()`; where the
+    /// expected type is written with four parameters, e.g.
+    /// `Oscillator<W, A, F, FW>`, `PB` is `NoBend`. Type inference does not
+    /// fall back to that default, so a bare `Oscillator::new(...)` needs
+    /// `PB` to be fixed by its context.
     #[inline]
-    pub fn new(waveform: W, amplitude: A, frequency: F, freq_warp: FW) -> Oscillator<W, A, F, FW> {
+    pub fn new(waveform: W, amplitude: A, frequency: F, freq_warp: FW) -> Oscillator<W, A, F, FW, PB> where
+        PB: Default,
+    {
         Oscillator {
             waveform: waveform,
             amplitude: amplitude,
             frequency: frequency,
             freq_warp: freq_warp,
+            phase_bend: PB::default(),
             is_muted: false,
         }
     }
 
     /// Waveform builder method.
     #[inline]
-    pub fn waveform<WNew>(self, waveform: WNew) -> Oscillator<WNew, A, F, FW> {
-        let Oscillator { amplitude, frequency, freq_warp, is_muted, .. } = self;
+    pub fn waveform<WNew>(self, waveform: WNew) -> Oscillator<WNew, A, F, FW, PB> {
+        let Oscillator { amplitude, frequency, freq_warp, phase_bend, is_muted, .. } = self;
         Oscillator {
             waveform: waveform,
-            amplitude: amplitude, 
+            amplitude: amplitude,
             frequency: frequency,
             freq_warp: freq_warp,
+            phase_bend: phase_bend,
             is_muted: is_muted,
         }
     }
 
     /// Amplitude envelope builder method.
     #[inline]
-    pub fn amplitude<ANew>(self, amplitude: ANew) -> Oscillator<W, ANew, F, FW> {
-        let Oscillator { waveform, frequency, freq_warp, is_muted, .. } = self;
+    pub fn amplitude<ANew>(self, amplitude: ANew) -> Oscillator<W, ANew, F, FW, PB> {
+        let Oscillator { waveform, frequency, freq_warp, phase_bend, is_muted, .. } = self;
         Oscillator {
             waveform: waveform,
-            amplitude: amplitude, 
+            amplitude: amplitude,
             frequency: frequency,
             freq_warp: freq_warp,
+            phase_bend: phase_bend,
             is_muted: is_muted,
         }
     }
 
     /// Amplitude envelope builder method.
     #[inline]
-    pub fn frequency<FNew>(self, frequency: FNew) -> Oscillator<W, A, FNew, FW> {
-        let Oscillator { waveform, amplitude, freq_warp, is_muted, .. } = self;
+    pub fn frequency<FNew>(self, frequency: FNew) -> Oscillator<W, A, FNew, FW, PB> {
+        let Oscillator { waveform, amplitude, freq_warp, phase_bend, is_muted, .. } = self;
+        Oscillator {
+            waveform: waveform,
+            amplitude: amplitude,
+            frequency: frequency,
+            freq_warp: freq_warp,
+            phase_bend: phase_bend,
+            is_muted: is_muted,
+        }
+    }
+
+    /// Phase bend builder method.
+    #[inline]
+    pub fn bend<PBNew>(self, phase_bend: PBNew) -> Oscillator<W, A, F, FW, PBNew> {
+        let Oscillator { waveform, amplitude, frequency, freq_warp, is_muted, .. } = self;
         Oscillator {
             waveform: waveform,
-            amplitude: amplitude, 
+            amplitude: amplitude,
             frequency: frequency,
             freq_warp: freq_warp,
+            phase_bend: phase_bend,
             is_muted: is_muted,
         }
     }
@@ -89,7 +121,9 @@ impl<W, A, F, FW> Oscillator<W, A, F, FW> {
     pub fn amp_at(&self, phase: f64, playhead_perc: f64) -> f32 where
         A: Amplitude,
         W: Waveform,
+        PB: PhaseBend,
     {
+        let phase = self.phase_bend.bend_phase(phase - phase.floor());
         self.waveform.amp_at_phase(phase) * self.amplitude.amp_at_playhead(playhead_perc)
     }
 
@@ -114,4 +148,3 @@ impl<W, A, F, FW> Oscillator<W, A, F, FW> {
     }
 
 }
-
@@ -0,0 +1,140 @@
+//!
+//! Phase bending - reshaping the phase read by a Waveform, as opposed to
+//! `FreqWarp` which reshapes the frequency driving phase advancement.
+//!
+
+/// A type that can remap the phase passed into `Waveform::amp_at_phase`,
+/// stretching or skewing a cycle without changing its pitch. Implementors
+/// must return a value in `[0,1)` for any input phase in `[0,1)`.
+pub trait PhaseBend {
+
+    /// Remap `phase` to the phase that should actually be sampled.
+    fn bend_phase(&self, phase: f64) -> f64;
+
+}
+
+
+/// The identity PhaseBend - returns the phase unchanged. Used as the
+/// default `PB` type for Oscillators that have no need to bend phase.
+#[derive(Debug, Clone, Copy, Default, RustcEncodable, RustcDecodable)]
+pub struct NoBend;
+
+impl PhaseBend for NoBend {
+
+    #[inline]
+    fn bend_phase(&self, phase: f64) -> f64 {
+        phase
+    }
+
+}
+
+
+/// A single draggable control point bending the input-to-output phase
+/// curve, modeled as a quadratic Bezier from `(0,0)` through `(x,y)` to
+/// `(1,1)`. Dragging the handle onto the diagonal (`x == y`) collapses the
+/// curve back to the identity.
+#[derive(Debug, Clone, Copy, RustcEncodable, RustcDecodable)]
+pub struct Bend {
+    x: f64,
+    y: f64,
+}
+
+impl Bend {
+
+    /// Bend constructor. The control point is clamped to `[0,1]` on both
+    /// axes so the curve can't be dragged outside the unit square.
+    #[inline]
+    pub fn new(x: f64, y: f64) -> Bend {
+        Bend { x: x.max(0.0).min(1.0), y: y.max(0.0).min(1.0) }
+    }
+
+    #[inline]
+    fn curve_y(&self, t: f64) -> f64 {
+        let u = 1.0 - t;
+        2.0 * u * t * self.y + t * t
+    }
+
+    /// Solve `curve_x(t) == phase` for `t` in closed form, where
+    /// `curve_x(t) = 2(1-t) t x + t^2`. Expanding gives the quadratic
+    /// `(1 - 2x) t^2 + 2x t - phase = 0` (linear when `x == 0.5`);
+    /// `curve_x` is monotonic for a control point inside the unit square,
+    /// so exactly one root lands in `[0,1]`.
+    #[inline]
+    fn solve_t(&self, phase: f64) -> f64 {
+        let a = 1.0 - 2.0 * self.x;
+        let b = 2.0 * self.x;
+        let c = -phase;
+
+        if a.abs() < 1e-12 {
+            return phase / b;
+        }
+
+        let disc = (b * b - 4.0 * a * c).max(0.0);
+        let sqrt_disc = disc.sqrt();
+        let t1 = (-b + sqrt_disc) / (2.0 * a);
+        let t2 = (-b - sqrt_disc) / (2.0 * a);
+
+        if t1 >= 0.0 && t1 <= 1.0 { t1 } else { t2 }
+    }
+
+}
+
+impl Default for Bend {
+    #[inline]
+    fn default() -> Bend {
+        Bend::new(0.5, 0.5)
+    }
+}
+
+impl PhaseBend for Bend {
+
+    fn bend_phase(&self, phase: f64) -> f64 {
+        if (self.x - self.y).abs() < 1e-12 {
+            return phase;
+        }
+
+        let t = self.solve_t(phase).max(0.0).min(1.0);
+        self.curve_y(t).max(0.0).min(1.0 - ::std::f64::EPSILON)
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_bend_is_always_identity() {
+        for i in 0..10 {
+            let phase = i as f64 / 10.0;
+            assert_eq!(NoBend.bend_phase(phase), phase);
+        }
+    }
+
+    #[test]
+    fn control_point_on_diagonal_is_identity() {
+        let bend = Bend::new(0.37, 0.37);
+        for i in 0..10 {
+            let phase = i as f64 / 10.0;
+            assert_eq!(bend.bend_phase(phase), phase);
+        }
+    }
+
+    #[test]
+    fn bend_phase_stays_in_unit_range() {
+        let bend = Bend::new(0.1, 0.9);
+        for i in 0..100 {
+            let phase = i as f64 / 100.0;
+            let bent = bend.bend_phase(phase);
+            assert!(bent >= 0.0 && bent < 1.0, "bent phase {} out of [0,1)", bent);
+        }
+    }
+
+    #[test]
+    fn endpoints_are_fixed() {
+        let bend = Bend::new(0.2, 0.8);
+        assert!(bend.bend_phase(0.0) < 1e-6);
+        assert!(bend.bend_phase(1.0) > 1.0 - 1e-6 && bend.bend_phase(1.0) < 1.0);
+    }
+}
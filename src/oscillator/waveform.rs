@@ -0,0 +1,337 @@
+//!
+//! Waveforms for the Oscillator.
+//!
+
+use std::cell::Cell;
+use std::f64::consts::PI;
+use std::sync::OnceLock;
+
+const TAU: f64 = PI * 2.0;
+
+
+/// A type that can produce an amplitude for a given phase in `[0,1)`, and
+/// optionally adjust the hz used to advance that phase.
+pub trait Waveform {
+
+    /// The amplitude (typically in `[-1,1]`) at the given phase.
+    fn amp_at_phase(&self, phase: f64) -> f32;
+
+    /// A hook allowing the waveform to adjust the hz used for phase
+    /// advancement before it reaches the Oscillator's FreqWarp. Most
+    /// waveforms pass it through unchanged.
+    #[inline]
+    fn process_hz(&self, hz: f64) -> f64 {
+        hz
+    }
+
+}
+
+
+/// Number of entries in the cosine lookup table, covering a full TAU
+/// period. A 513th guard entry equal to the first lets interpolation read
+/// one past the final sample without a modulo.
+const TABLE_SIZE: usize = 512;
+
+static TABLE: OnceLock<[f64; TABLE_SIZE + 1]> = OnceLock::new();
+
+/// Build the cosine table on first use and hand back a reference to it.
+fn cos_table() -> &'static [f64; TABLE_SIZE + 1] {
+    TABLE.get_or_init(|| {
+        let mut table = [0.0; TABLE_SIZE + 1];
+        for i in 0..TABLE_SIZE {
+            table[i] = (i as f64 / TABLE_SIZE as f64 * TAU).cos();
+        }
+        table[TABLE_SIZE] = table[0];
+        table
+    })
+}
+
+/// A fast approximation of `(TAU * phase).cos()`, accurate to the
+/// resolution of the 512-entry lookup table, linearly interpolated between
+/// entries. `phase` is wrapped into `[0,1)` so callers may pass any phase.
+#[inline]
+pub fn fast_cos(phase: f64) -> f64 {
+    let table = cos_table();
+    let wrapped = phase - phase.floor();
+    let scaled = wrapped * TABLE_SIZE as f64;
+    let index = (scaled as usize).min(TABLE_SIZE - 1);
+    let frac = scaled - index as f64;
+    table[index] + (table[index + 1] - table[index]) * frac
+}
+
+/// A fast approximation of `(TAU * phase).sin()`, built on `fast_cos` via
+/// the identity `sin(x) = cos(x - pi/2)`.
+#[inline]
+pub fn fast_sin(phase: f64) -> f64 {
+    fast_cos(phase - 0.25)
+}
+
+
+/// A pure sine wave.
+#[derive(Debug, Clone, Copy, Default, RustcEncodable, RustcDecodable)]
+pub struct Sine;
+
+impl Waveform for Sine {
+
+    #[inline]
+    fn amp_at_phase(&self, phase: f64) -> f32 {
+        fast_sin(phase) as f32
+    }
+
+}
+
+
+/// A symmetric triangle wave, rising from -1 to 1 over the first half of
+/// the phase and falling back over the second half.
+///
+/// Deliberately left on its closed-form floor/abs formula rather than
+/// `fast_sin`/`fast_cos` - it has no transcendental call to amortize, so a
+/// table lookup would only add an indirection for no speedup.
+#[derive(Debug, Clone, Copy, Default, RustcEncodable, RustcDecodable)]
+pub struct Triangle;
+
+impl Waveform for Triangle {
+
+    #[inline]
+    fn amp_at_phase(&self, phase: f64) -> f32 {
+        let amp = 4.0 * (phase - (phase + 0.5).floor()).abs() - 1.0;
+        amp as f32
+    }
+
+}
+
+
+/// Smallest distance a duty cycle is allowed from 0.0 or 1.0, keeping
+/// `Pulse` from collapsing into a constant-DC output.
+const MIN_DUTY: f64 = 0.001;
+
+/// A pulse (rectangular) wave with a configurable duty cycle - the
+/// fraction of each period spent at `+1.0` before dropping to `-1.0`.
+/// NES-style chiptune voices sweep this between 12.5%, 25%, 50% and 75%;
+/// `0.5` gives a standard square wave.
+#[derive(Debug, Clone, Copy, RustcEncodable, RustcDecodable)]
+pub struct Pulse {
+    duty: f64,
+}
+
+impl Pulse {
+
+    /// Pulse constructor. `duty` is clamped to `[MIN_DUTY, 1.0 - MIN_DUTY]`
+    /// so the waveform can never settle on a constant output.
+    #[inline]
+    pub fn new(duty: f64) -> Pulse {
+        Pulse { duty: duty.max(MIN_DUTY).min(1.0 - MIN_DUTY) }
+    }
+
+    /// The pulse's current duty cycle.
+    #[inline]
+    pub fn duty(&self) -> f64 {
+        self.duty
+    }
+
+}
+
+impl Default for Pulse {
+    #[inline]
+    fn default() -> Pulse {
+        Pulse::new(0.5)
+    }
+}
+
+impl Waveform for Pulse {
+
+    #[inline]
+    fn amp_at_phase(&self, phase: f64) -> f32 {
+        let phase = phase - phase.floor();
+        if phase < self.duty { 1.0 } else { -1.0 }
+    }
+
+}
+
+
+/// A low-frequency oscillator that modulates pitch (vibrato). Its own
+/// phase is stored externally, threaded the same way `FreqWarp`'s
+/// `freq_warp_phase` is, so a single `PitchLfo` value stays reusable across
+/// the oscillators sharing it.
+#[derive(Debug, Clone, Copy, RustcEncodable, RustcDecodable)]
+pub struct PitchLfo {
+    /// LFO rate in Hz.
+    pub rate_hz: f64,
+    /// Modulation depth, in semitones.
+    pub depth_semitones: f64,
+}
+
+impl PitchLfo {
+
+    /// PitchLfo constructor.
+    #[inline]
+    pub fn new(rate_hz: f64, depth_semitones: f64) -> PitchLfo {
+        PitchLfo { rate_hz: rate_hz, depth_semitones: depth_semitones }
+    }
+
+    /// Advance `lfo_phase` by one sample and return the multiplier that
+    /// should be applied to `note_freq_multi` for this sample.
+    #[inline]
+    pub fn step(&self, sample_hz: f64, lfo_phase: &mut f64) -> f64 {
+        *lfo_phase = (*lfo_phase + self.rate_hz / sample_hz).fract();
+        let semitones = self.depth_semitones * fast_sin(*lfo_phase);
+        2.0_f64.powf(semitones / 12.0)
+    }
+
+}
+
+
+/// A deterministic xorshift64* PRNG, kept as part of the noise waveforms
+/// rather than pulled in from a `rand` dependency so noise is reproducible
+/// from a seed alone.
+#[inline]
+fn xorshift64star(state: &Cell<u64>) -> u64 {
+    let mut x = state.get();
+    x ^= x >> 12;
+    x ^= x << 25;
+    x ^= x >> 27;
+    state.set(x);
+    x.wrapping_mul(0x2545F4914F6CDD1D)
+}
+
+/// Scale a raw xorshift64* output down to `[-1,1]`.
+#[inline]
+fn unit_sample(raw: u64) -> f64 {
+    ((raw >> 11) as f64 / (1u64 << 53) as f64) * 2.0 - 1.0
+}
+
+
+/// Uniform white noise. `amp_at_phase` ignores the incoming phase entirely
+/// and returns a fresh sample in `[-1,1]` per call, so each invocation
+/// should be treated as advancing one sample rather than reading a
+/// position within a cycle. Carries its own xorshift seed (in a `Cell`, so
+/// `amp_at_phase` can stay `&self`) to keep output deterministic.
+#[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
+pub struct WhiteNoise {
+    seed: Cell<u64>,
+}
+
+impl WhiteNoise {
+
+    /// WhiteNoise constructor from a seed. A seed of `0` would leave
+    /// xorshift stuck at `0` forever, so it's substituted with a fixed
+    /// nonzero fallback.
+    #[inline]
+    pub fn new(seed: u64) -> WhiteNoise {
+        let seed = if seed == 0 { 0x9E3779B97F4A7C15 } else { seed };
+        WhiteNoise { seed: Cell::new(seed) }
+    }
+
+}
+
+impl Default for WhiteNoise {
+    #[inline]
+    fn default() -> WhiteNoise {
+        WhiteNoise::new(0x2545F4914F6CDD1D)
+    }
+}
+
+impl Waveform for WhiteNoise {
+
+    #[inline]
+    fn amp_at_phase(&self, _phase: f64) -> f32 {
+        unit_sample(xorshift64star(&self.seed)) as f32
+    }
+
+}
+
+
+/// Brown (red) noise - white noise integrated through an accumulator clamped
+/// to `[-1,1]`,
+/// giving the characteristic -6dB/octave spectrum. Like `WhiteNoise`, each
+/// `amp_at_phase` call is treated as one sample rather than a read at the
+/// given phase.
+#[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
+pub struct BrownNoise {
+    white: WhiteNoise,
+    /// How far one white-noise sample nudges the accumulator; smaller
+    /// values integrate more slowly (darker, smoother noise).
+    step: f64,
+    last: Cell<f64>,
+}
+
+impl BrownNoise {
+
+    /// BrownNoise constructor from a seed and integration step.
+    #[inline]
+    pub fn new(seed: u64, step: f64) -> BrownNoise {
+        BrownNoise {
+            white: WhiteNoise::new(seed),
+            step: step,
+            last: Cell::new(0.0),
+        }
+    }
+
+}
+
+impl Default for BrownNoise {
+    #[inline]
+    fn default() -> BrownNoise {
+        BrownNoise::new(0x2545F4914F6CDD1D, 0.1)
+    }
+}
+
+impl Waveform for BrownNoise {
+
+    #[inline]
+    fn amp_at_phase(&self, phase: f64) -> f32 {
+        let white = self.white.amp_at_phase(phase) as f64;
+        let next = (self.last.get() + white * self.step).max(-1.0).min(1.0);
+        self.last.set(next);
+        next as f32
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    extern crate test;
+
+    use self::test::Bencher;
+    use super::*;
+
+    #[test]
+    fn pulse_wraps_phase_across_many_cycles() {
+        let pulse = Pulse::new(0.5);
+        // Drive phase well past 1.0, as Oscillator::next_phase does for any
+        // note longer than a single cycle - it never wraps phase itself.
+        for cycle in 0..10 {
+            let base = cycle as f64;
+            assert_eq!(pulse.amp_at_phase(base + 0.1), 1.0);
+            assert_eq!(pulse.amp_at_phase(base + 0.9), -1.0);
+        }
+    }
+
+    #[test]
+    fn fast_sin_matches_f64_sin() {
+        for i in 0..1000 {
+            let phase = i as f64 / 1000.0;
+            let expected = (TAU * phase).sin();
+            assert!((fast_sin(phase) - expected).abs() < 1e-3);
+        }
+    }
+
+    #[bench]
+    fn bench_fast_sin(b: &mut Bencher) {
+        let mut phase = 0.0;
+        b.iter(|| {
+            phase = (phase + 0.0137).fract();
+            fast_sin(phase)
+        });
+    }
+
+    #[bench]
+    fn bench_f64_sin(b: &mut Bencher) {
+        let mut phase = 0.0;
+        b.iter(|| {
+            phase = (phase + 0.0137).fract();
+            (TAU * phase).sin()
+        });
+    }
+}